@@ -11,6 +11,7 @@ use iced::{Application, Color, Command, Element, Font, Length, Pixels, Settings,
 use log::{info, Level};
 use plotters::prelude::ChartBuilder;
 use plotters::style::text_anchor::{HPos, Pos, VPos};
+use plotters::style::RGBColor;
 use plotters_iced::plotters_backend::BackendColor;
 use plotters_iced::{Chart, ChartWidget, DrawingBackend, Renderer};
 use reqwest;
@@ -89,7 +90,14 @@ struct SensorState {
 #[derive(Debug)]
 struct App {
     state: AppState,
-    theme: ThemeType,
+    theme: Palette,
+    themes: Vec<Palette>,
+    selected_theme: usize,
+    auto_refresh: bool,
+    refresh_interval: u64,
+    /// CO2 levels splitting "good"/"moderate" and "moderate"/"unhealthy".
+    thresholds: [u16; 2],
+    active_tab: Tab,
 }
 
 #[derive(Debug)]
@@ -106,14 +114,235 @@ enum Message {
     BottomSliderChanged(u16),
     TopSliderChanged(u16),
     FontLoaded(Result<(), font::Error>),
-    ThemeChanged(ThemeType),
+    ThemeChanged(usize),
+    Co2Toggled(bool),
+    TvocToggled(bool),
+    AutoRefreshToggled(bool),
+    RefreshIntervalChanged(u64),
+    ModerateThresholdChanged(String),
+    UnhealthyThresholdChanged(String),
+    TabSelected(Tab),
+    Export(Format),
     None,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum ThemeType {
-    Light,
-    Dark,
+/// The metric a chart plots as its primary series.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Metric {
+    Co2,
+    Tvoc,
+    Quality,
+}
+
+impl Metric {
+    fn value(&self, state: &SensorState) -> f32 {
+        match self {
+            Metric::Co2 => state.co2 as f32,
+            Metric::Tvoc => state.tvoc as f32,
+            Metric::Quality => state.qi as u8 as f32,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Metric::Co2 => "CO2",
+            Metric::Tvoc => "TVOC",
+            Metric::Quality => "Quality",
+        }
+    }
+
+    fn color(&self, palette: &Palette) -> RGBColor {
+        match self {
+            Metric::Co2 => palette.co2,
+            Metric::Tvoc => palette.tvoc,
+            Metric::Quality => RGBColor(90, 170, 90),
+        }
+    }
+}
+
+/// The currently selected view in the tab bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    Current,
+    Co2,
+    Tvoc,
+    Quality,
+}
+
+impl Tab {
+    /// The metric a chart should plot while this tab is active. The `Current`
+    /// tab shows no chart, so it defaults to CO2.
+    fn metric(&self) -> Metric {
+        match self {
+            Tab::Tvoc => Metric::Tvoc,
+            Tab::Quality => Metric::Quality,
+            _ => Metric::Co2,
+        }
+    }
+}
+
+/// Summary statistics over a metric across the selected range.
+#[derive(Debug, Clone, Copy)]
+struct Stats {
+    min: f32,
+    max: f32,
+    mean: f32,
+    latest: f32,
+}
+
+/// Compute min/max/mean/latest of `metric` over `data`. `data` is never empty
+/// in the `Loaded` state, so the `unwrap` below always holds.
+fn stats(data: &[SensorState], metric: Metric) -> Stats {
+    let values: Vec<f32> = data.iter().map(|state| metric.value(state)).collect();
+    let min = values.iter().cloned().fold(f32::INFINITY, f32::min);
+    let max = values.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+    let mean = values.iter().sum::<f32>() / values.len() as f32;
+    let latest = *values.last().unwrap();
+    Stats {
+        min,
+        max,
+        mean,
+        latest,
+    }
+}
+
+/// A full colour palette for the UI and the chart.
+///
+/// Built-in palettes (`Light`/`Dark`) are always present; further palettes are
+/// read from `~/.config/co2-app/themes/*.toml` on startup.
+#[derive(Debug, Clone, PartialEq)]
+struct Palette {
+    name: String,
+    /// Which iced base theme (`Theme::Dark`/`Theme::Light`) to pair with.
+    dark_base: bool,
+    background: RGBColor,
+    plot_line: RGBColor,
+    co2: RGBColor,
+    tvoc: RGBColor,
+    label: RGBColor,
+}
+
+impl Palette {
+    fn light() -> Self {
+        Palette {
+            name: "Light".to_string(),
+            dark_base: false,
+            background: RGBColor(255, 255, 255),
+            plot_line: RGBColor(100, 100, 100),
+            co2: RGBColor(30, 50, 200),
+            tvoc: RGBColor(200, 80, 30),
+            label: RGBColor(0, 0, 0),
+        }
+    }
+
+    fn dark() -> Self {
+        Palette {
+            name: "Dark".to_string(),
+            dark_base: true,
+            background: RGBColor(30, 30, 30),
+            plot_line: RGBColor(150, 150, 150),
+            co2: RGBColor(51, 89, 218),
+            tvoc: RGBColor(230, 130, 70),
+            label: RGBColor(255, 255, 255),
+        }
+    }
+
+    fn base(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "light" => Some(Palette::light()),
+            "dark" => Some(Palette::dark()),
+            _ => None,
+        }
+    }
+
+    fn label_rgb(&self) -> (u8, u8, u8) {
+        (self.label.0, self.label.1, self.label.2)
+    }
+}
+
+/// A palette as read from a TOML file. Every colour is optional so a theme can
+/// `inherits = "dark"` and override only the few values it cares about.
+#[derive(Deserialize, Debug)]
+struct ThemeFile {
+    name: String,
+    inherits: Option<String>,
+    dark_base: Option<bool>,
+    background: Option<String>,
+    plot_line: Option<String>,
+    co2: Option<String>,
+    tvoc: Option<String>,
+    label: Option<String>,
+}
+
+/// Parse a `#rrggbb` (or bare `rrggbb`) hex colour.
+fn parse_hex(s: &str) -> Option<RGBColor> {
+    let s = s.trim().trim_start_matches('#');
+    if s.len() != 6 {
+        return None;
+    }
+    let r = u8::from_str_radix(&s[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&s[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&s[4..6], 16).ok()?;
+    Some(RGBColor(r, g, b))
+}
+
+impl ThemeFile {
+    fn into_palette(self) -> Palette {
+        let mut palette = self
+            .inherits
+            .as_deref()
+            .and_then(Palette::base)
+            .unwrap_or_else(Palette::dark);
+
+        palette.name = self.name;
+        if let Some(dark_base) = self.dark_base {
+            palette.dark_base = dark_base;
+        }
+        if let Some(color) = self.background.as_deref().and_then(parse_hex) {
+            palette.background = color;
+        }
+        if let Some(color) = self.plot_line.as_deref().and_then(parse_hex) {
+            palette.plot_line = color;
+        }
+        if let Some(color) = self.co2.as_deref().and_then(parse_hex) {
+            palette.co2 = color;
+        }
+        if let Some(color) = self.tvoc.as_deref().and_then(parse_hex) {
+            palette.tvoc = color;
+        }
+        if let Some(color) = self.label.as_deref().and_then(parse_hex) {
+            palette.label = color;
+        }
+        palette
+    }
+}
+
+/// Enumerate the available palettes: the two built-ins followed by any theme
+/// files found under `~/.config/co2-app/themes`.
+fn load_themes() -> Vec<Palette> {
+    let mut themes = vec![Palette::light(), Palette::dark()];
+
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(home) = std::env::var_os("HOME") {
+        let dir = std::path::Path::new(&home).join(".config/co2-app/themes");
+        if let Ok(entries) = std::fs::read_dir(&dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().and_then(|ext| ext.to_str()) != Some("toml") {
+                    continue;
+                }
+                match std::fs::read_to_string(&path)
+                    .ok()
+                    .and_then(|contents| toml::from_str::<ThemeFile>(&contents).ok())
+                {
+                    Some(theme) => themes.push(theme.into_palette()),
+                    None => info!("Skipping unreadable theme file {:?}", path),
+                }
+            }
+        }
+    }
+
+    themes
 }
 
 #[derive(Debug, Clone)]
@@ -129,6 +358,94 @@ impl From<reqwest::Error> for Error {
     }
 }
 
+/// The on-disk format the selected range can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Csv,
+    Json,
+}
+
+impl Format {
+    fn extension(&self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Json => "json",
+        }
+    }
+
+    fn mime(&self) -> &'static str {
+        match self {
+            Format::Csv => "text/csv",
+            Format::Json => "application/json",
+        }
+    }
+}
+
+/// Render a range of samples as CSV or JSON. Columns are `time, status, qi,
+/// tvoc, co2`; the enums are emitted as their numeric representation.
+fn serialize(data: &[SensorState], format: Format) -> String {
+    match format {
+        Format::Csv => {
+            let mut out = String::from("time,status,qi,tvoc,co2\n");
+            for state in data {
+                out.push_str(&format!(
+                    "{},{},{},{},{}\n",
+                    state.time.format("%Y-%m-%d %H:%M:%S"),
+                    state.status as u8,
+                    state.qi as u8,
+                    state.tvoc,
+                    state.co2,
+                ));
+            }
+            out
+        }
+        Format::Json => {
+            let rows: Vec<String> = data
+                .iter()
+                .map(|state| {
+                    format!(
+                        "{{\"time\":\"{}\",\"status\":{},\"qi\":{},\"tvoc\":{},\"co2\":{}}}",
+                        state.time.format("%Y-%m-%d %H:%M:%S"),
+                        state.status as u8,
+                        state.qi as u8,
+                        state.tvoc,
+                        state.co2,
+                    )
+                })
+                .collect();
+            format!("[{}]", rows.join(","))
+        }
+    }
+}
+
+/// Write the exported range to `co2-export.<ext>` in the working directory.
+#[cfg(not(target_arch = "wasm32"))]
+async fn export(contents: String, format: Format) {
+    let path = format!("co2-export.{}", format.extension());
+    match std::fs::write(&path, contents) {
+        Ok(()) => info!("Exported selection to {path}"),
+        Err(err) => info!("Failed to export selection: {err}"),
+    }
+}
+
+/// On wasm there is no filesystem, so trigger a browser download via a data URL.
+#[cfg(target_arch = "wasm32")]
+async fn export(contents: String, format: Format) {
+    let url = format!(
+        "data:{};charset=utf-8,{}",
+        format.mime(),
+        js_sys::encode_uri_component(&contents)
+    );
+    let document = web_sys::window().unwrap().document().unwrap();
+    let anchor = document.create_element("a").unwrap();
+    anchor.set_attribute("href", &url).unwrap();
+    anchor
+        .set_attribute("download", &format!("co2-export.{}", format.extension()))
+        .unwrap();
+    let anchor: web_sys::HtmlElement = wasm_bindgen::JsCast::dyn_into(anchor).unwrap();
+    anchor.click();
+}
+
 async fn load() -> Result<Vec<SensorState>, Error> {
     let response: Vec<SensorState> = reqwest::Client::new()
         .get(HISTORY_URL)
@@ -141,13 +458,97 @@ async fn load() -> Result<Vec<SensorState>, Error> {
 
 const HISTORY_URL: &str = "https://sienkiewiczapi.duckdns.org/co2/api/history";
 
+/// Above this many points in the selected range, a series is decimated with
+/// LTTB before being handed to `LineSeries`.
+const DOWNSAMPLE_THRESHOLD: usize = 500;
+
+/// Largest-Triangle-Three-Buckets downsampling.
+///
+/// Returns the indices of the points to keep (using the sample index as `x`),
+/// preserving visual peaks and valleys far better than naive stride sampling.
+/// If `n <= threshold` or `threshold < 3` the input is returned unchanged.
+fn lttb_indices(values: &[f32], threshold: usize) -> Vec<usize> {
+    let n = values.len();
+    if n <= threshold || threshold < 3 {
+        return (0..n).collect();
+    }
+
+    let mut sampled = Vec::with_capacity(threshold);
+    // The first and last points are always kept.
+    sampled.push(0);
+
+    let bucket_size = (n - 2) as f32 / (threshold - 2) as f32;
+    let mut a = 0usize; // index of the last selected point
+
+    for i in 0..(threshold - 2) {
+        // Average point of the *next* bucket is the triangle's far vertex `c`.
+        let next_start = ((i + 1) as f32 * bucket_size) as usize + 1;
+        let next_end = (((i + 2) as f32 * bucket_size) as usize + 1).min(n);
+        let next_len = (next_end - next_start).max(1);
+        let mut c_x = 0f32;
+        let mut c_y = 0f32;
+        for j in next_start..next_end {
+            c_x += j as f32;
+            c_y += values[j];
+        }
+        c_x /= next_len as f32;
+        c_y /= next_len as f32;
+
+        // Pick the point in the current bucket that forms the largest triangle.
+        let bucket_start = (i as f32 * bucket_size) as usize + 1;
+        let bucket_end = (((i + 1) as f32 * bucket_size) as usize + 1).min(n);
+
+        let a_x = a as f32;
+        let a_y = values[a];
+
+        let mut best = bucket_start;
+        let mut best_area = -1f32;
+        for p in bucket_start..bucket_end {
+            let area =
+                0.5 * ((a_x - c_x) * (values[p] - a_y) - (a_x - p as f32) * (c_y - a_y)).abs();
+            if area > best_area {
+                best_area = area;
+                best = p;
+            }
+        }
+
+        sampled.push(best);
+        a = best;
+    }
+
+    sampled.push(n - 1);
+    sampled
+}
+
+/// Decimate a metric of the selected range with [`lttb_indices`], returning
+/// `(time, value)` pairs ready for a `LineSeries`. Untouched below the threshold.
+fn downsample<F>(data: &[SensorState], value: F) -> Vec<(DateTime<Local>, f32)>
+where
+    F: Fn(&SensorState) -> f32,
+{
+    if data.len() <= DOWNSAMPLE_THRESHOLD {
+        return data.iter().map(|state| (state.time, value(state))).collect();
+    }
+    let values: Vec<f32> = data.iter().map(&value).collect();
+    lttb_indices(&values, DOWNSAMPLE_THRESHOLD)
+        .into_iter()
+        .map(|i| (data[i].time, value(&data[i])))
+        .collect()
+}
+
 #[derive(Debug)]
 struct CO2Chart {
     cache: Cache,
     data: Vec<SensorState>,
     bottom: u16,
     top: u16,
-    theme: ThemeType,
+    theme: Palette,
+    show_co2: bool,
+    show_tvoc: bool,
+    thresholds: [u16; 2],
+    /// The primary metric to plot. On the CO2 tab this keeps the dual-axis
+    /// view with TVOC overlay and danger zones; other metrics plot alone.
+    metric: Metric,
 }
 
 impl Chart<Message> for CO2Chart {
@@ -165,27 +566,46 @@ impl Chart<Message> for CO2Chart {
     fn build_chart<DB: DrawingBackend>(&self, _state: &Self::State, mut builder: ChartBuilder<DB>) {
         use plotters::prelude::*;
 
-        let plot_line_color: RGBColor = match self.theme {
-            ThemeType::Light => RGBColor(100, 100, 100),
-            ThemeType::Dark => RGBColor(150, 150, 150),
-        };
+        let plot_line_color: RGBColor = self.theme.plot_line;
+        let co2_color: RGBColor = self.theme.co2;
+        let tvoc_color: RGBColor = self.theme.tvoc;
+        let label_rgb = self.theme.label_rgb();
+        let primary_color = self.metric.color(&self.theme);
 
-        let series_color: RGBColor = match self.theme {
-            ThemeType::Light => RGBColor(30, 50, 200),
-            ThemeType::Dark => RGBColor(51, 89, 218),
-        };
+        // The CO2 tab keeps the rich dual-axis layout (danger zones + TVOC
+        // overlay); the other metric tabs plot their single series alone.
+        let is_co2 = self.metric == Metric::Co2;
+        let show_overlay = is_co2 && self.show_tvoc;
 
         let data = self.data[self.bottom as usize..=self.top as usize].to_vec();
 
+        let start = data.first().unwrap().time;
+        let end = data.last().unwrap().time;
+
+        // TVOC lives on a much smaller scale than CO2, so it gets its own
+        // right-hand axis sized to the selected range.
+        let tvoc_max = data
+            .iter()
+            .map(|state| state.tvoc)
+            .max()
+            .unwrap_or(0)
+            .max(10) as f32;
+
+        // Y range for the primary series.
+        let primary_range = match self.metric {
+            Metric::Co2 => 440_f32..2000_f32,
+            Metric::Tvoc => 0_f32..tvoc_max,
+            Metric::Quality => 0_f32..5_f32,
+        };
+
         let mut chart = builder
             .x_label_area_size(28_i32)
             .y_label_area_size(28_i32)
+            .right_y_label_area_size(if show_overlay { 36_i32 } else { 0_i32 })
             .margin(8_i32)
-            .build_cartesian_2d(
-                data.first().unwrap().time..data.last().unwrap().time,
-                440_f32..2000_f32,
-            )
-            .expect("Failed to build chart");
+            .build_cartesian_2d(start..end, primary_range)
+            .expect("Failed to build chart")
+            .set_secondary_coord(start..end, 0_f32..tvoc_max);
 
         chart
             .configure_mesh()
@@ -194,10 +614,7 @@ impl Chart<Message> for CO2Chart {
                 font: ("Montserrat", 12).into_font(),
                 color: BackendColor {
                     alpha: 1.,
-                    rgb: match self.theme {
-                        ThemeType::Light => (0, 0, 0),
-                        ThemeType::Dark => (255, 255, 255),
-                    },
+                    rgb: label_rgb,
                 },
                 pos: Pos::new(HPos::Left, VPos::Top),
             })
@@ -205,13 +622,100 @@ impl Chart<Message> for CO2Chart {
             .bold_line_style(plot_line_color.mix(0.25))
             .axis_style(plot_line_color)
             .draw();
+
+        if show_overlay {
+            chart
+                .configure_secondary_axes()
+                .label_style(TextStyle {
+                    font: ("Montserrat", 12).into_font(),
+                    color: BackendColor {
+                        alpha: 1.,
+                        rgb: label_rgb,
+                    },
+                    pos: Pos::new(HPos::Left, VPos::Top),
+                })
+                .axis_style(tvoc_color)
+                .draw();
+        }
+
+        // Danger zones: a shaded band and a dashed line for each CO2 threshold,
+        // drawn behind the data so the line stays readable on top. Only the CO2
+        // view carries these, since the thresholds are CO2 levels.
+        if is_co2 {
+            // Normalize so an inverted text-input pair (moderate > unhealthy)
+            // still yields an ordered moderate/unhealthy band.
+            let [lo, hi] = {
+                let mut t = self.thresholds;
+                t.sort_unstable();
+                t
+            };
+            let moderate = (lo as f32).clamp(440_f32, 2000_f32);
+            let unhealthy = (hi as f32).clamp(440_f32, 2000_f32);
+            let moderate_color = RGBColor(230, 180, 0);
+            let unhealthy_color = RGBColor(220, 60, 60);
+
+            for (low, high, color) in [
+                (moderate, unhealthy, moderate_color),
+                (unhealthy, 2000_f32, unhealthy_color),
+            ] {
+                if high > low {
+                    chart
+                        .draw_series(std::iter::once(Rectangle::new(
+                            [(start, low), (end, high)],
+                            color.mix(0.12).filled(),
+                        )))
+                        .expect("Failed to draw danger zone");
+                }
+            }
+
+            for (level, line_color) in [(moderate, moderate_color), (unhealthy, unhealthy_color)] {
+                chart
+                    .draw_series(DashedLineSeries::new(
+                        vec![(start, level), (end, level)],
+                        8,
+                        6,
+                        line_color.mix(0.6).stroke_width(1),
+                    ))
+                    .expect("Failed to draw threshold line");
+            }
+        }
+
+        // The primary series. On the CO2 tab the `show_co2` toggle still hides
+        // it; the other tabs always draw their single metric.
+        if !is_co2 || self.show_co2 {
+            let metric = self.metric;
+            let points = downsample(&data, move |state| metric.value(state));
+            chart
+                .draw_series(LineSeries::new(points, primary_color))
+                .expect("Failed to draw data")
+                .label(self.metric.label())
+                .legend(move |(x, y)| {
+                    PathElement::new(vec![(x, y), (x + 16, y)], primary_color)
+                });
+        }
+
+        if show_overlay {
+            let tvoc_points = downsample(&data, |state| state.tvoc as f32);
+            chart
+                .draw_secondary_series(LineSeries::new(tvoc_points, tvoc_color))
+                .expect("Failed to draw data")
+                .label("TVOC")
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 16, y)], tvoc_color));
+        }
+
         chart
-            .draw_series(LineSeries::new(
-                data.iter()
-                    .map(|sensor_state| (sensor_state.time, sensor_state.co2 as f32)),
-                series_color,
-            ))
-            .expect("Failed to draw data");
+            .configure_series_labels()
+            .label_font(TextStyle {
+                font: ("Montserrat", 12).into_font(),
+                color: BackendColor {
+                    alpha: 1.,
+                    rgb: label_rgb,
+                },
+                pos: Pos::new(HPos::Left, VPos::Top),
+            })
+            .border_style(plot_line_color)
+            .draw()
+            .expect("Failed to draw legend");
     }
 }
 
@@ -234,17 +738,29 @@ impl Application for App {
     type Flags = ();
 
     fn theme(&self) -> Self::Theme {
-        match self.theme {
-            ThemeType::Dark => Theme::Dark,
-            ThemeType::Light => Theme::Light,
+        if self.theme.dark_base {
+            Theme::Dark
+        } else {
+            Theme::Light
+        }
+    }
+
+    fn subscription(&self) -> iced::Subscription<Self::Message> {
+        if self.auto_refresh {
+            iced::time::every(std::time::Duration::from_secs(self.refresh_interval))
+                .map(|_| Message::Load)
+        } else {
+            iced::Subscription::none()
         }
     }
 
     fn new(_flags: Self::Flags) -> (Self, iced::Command<Self::Message>) {
-        let theme = match dark_light::detect() {
-            dark_light::Mode::Light => ThemeType::Light,
-            _ => ThemeType::Dark,
+        let themes = load_themes();
+        let selected_theme = match dark_light::detect() {
+            dark_light::Mode::Light => 0,
+            _ => 1,
         };
+        let theme = themes[selected_theme].clone();
 
         #[cfg(target_arch = "wasm32")]
         {
@@ -267,6 +783,12 @@ impl Application for App {
             App {
                 state: AppState::Loading,
                 theme,
+                themes,
+                selected_theme,
+                auto_refresh: false,
+                refresh_interval: 30,
+                thresholds: [1000, 1500],
+                active_tab: Tab::Current,
             },
             Command::batch([
                 font::load(include_bytes!("../Montserrat-Regular.ttf").as_slice())
@@ -297,14 +819,35 @@ impl Application for App {
                     true => data_len - 60,
                     false => 0,
                 };
+                // Preserve the viewport and series toggles across an
+                // auto-refresh so a ticking wall display keeps the user's
+                // zoom selection and visible series.
+                let (bottom, top, show_co2, show_tvoc) = match &self.state {
+                    AppState::Loaded(_, old, old_len) => {
+                        // A viewport pinned to the latest sample follows new
+                        // data; a genuinely zoomed-in window is preserved.
+                        let top = if old.top == old_len.saturating_sub(1) {
+                            data_len - 1
+                        } else {
+                            old.top.min(data_len - 1)
+                        };
+                        let bottom = old.bottom.min(top.saturating_sub(2));
+                        (bottom, top, old.show_co2, old.show_tvoc)
+                    }
+                    _ => (bottom_val, data_len - 1, true, true),
+                };
                 self.state = AppState::Loaded(
                     last,
                     CO2Chart {
                         cache: Cache::new(),
                         data,
-                        bottom: bottom_val,
-                        top: data_len - 1,
-                        theme: self.theme,
+                        bottom,
+                        top,
+                        theme: self.theme.clone(),
+                        show_co2,
+                        show_tvoc,
+                        thresholds: self.thresholds,
+                        metric: self.active_tab.metric(),
                     },
                     data_len,
                 );
@@ -330,7 +873,11 @@ impl Application for App {
                             data: old_chart.data.to_vec(),
                             bottom: val,
                             top: old_chart.top,
-                            theme: self.theme,
+                            theme: self.theme.clone(),
+                            show_co2: old_chart.show_co2,
+                            show_tvoc: old_chart.show_tvoc,
+                            thresholds: self.thresholds,
+                            metric: old_chart.metric,
                         },
                         *data_len,
                     );
@@ -348,7 +895,11 @@ impl Application for App {
                             data: old_chart.data.to_vec(),
                             bottom: old_chart.bottom,
                             top: val,
-                            theme: self.theme,
+                            theme: self.theme.clone(),
+                            show_co2: old_chart.show_co2,
+                            show_tvoc: old_chart.show_tvoc,
+                            thresholds: self.thresholds,
+                            metric: old_chart.metric,
                         },
                         *data_len,
                     );
@@ -357,29 +908,88 @@ impl Application for App {
                 _ => Command::none(),
             },
             Message::FontLoaded(_) => Command::none(),
-            Message::ThemeChanged(theme) => {
-                self.theme = theme;
-                match &mut self.state {
-                    AppState::Loaded(_, chart, _) => {
-                        chart.theme = theme;
+            Message::ThemeChanged(index) => {
+                if let Some(palette) = self.themes.get(index) {
+                    self.selected_theme = index;
+                    self.theme = palette.clone();
+                    if let AppState::Loaded(_, chart, _) = &mut self.state {
+                        chart.theme = palette.clone();
+                        chart.cache.clear();
+                    }
+                }
+                Command::none()
+            }
+            Message::Co2Toggled(show) => {
+                if let AppState::Loaded(_, chart, _) = &mut self.state {
+                    chart.show_co2 = show;
+                    chart.cache.clear();
+                }
+                Command::none()
+            }
+            Message::TvocToggled(show) => {
+                if let AppState::Loaded(_, chart, _) = &mut self.state {
+                    chart.show_tvoc = show;
+                    chart.cache.clear();
+                }
+                Command::none()
+            }
+            Message::ModerateThresholdChanged(value) => {
+                if let Ok(parsed) = value.parse::<u16>() {
+                    self.thresholds[0] = parsed;
+                    if let AppState::Loaded(_, chart, _) = &mut self.state {
+                        chart.thresholds[0] = parsed;
+                        chart.cache.clear();
+                    }
+                }
+                Command::none()
+            }
+            Message::UnhealthyThresholdChanged(value) => {
+                if let Ok(parsed) = value.parse::<u16>() {
+                    self.thresholds[1] = parsed;
+                    if let AppState::Loaded(_, chart, _) = &mut self.state {
+                        chart.thresholds[1] = parsed;
                         chart.cache.clear();
                     }
-                    _ => {}
                 }
                 Command::none()
             }
+            Message::TabSelected(tab) => {
+                self.active_tab = tab;
+                if let AppState::Loaded(_, chart, _) = &mut self.state {
+                    chart.metric = tab.metric();
+                    chart.cache.clear();
+                }
+                Command::none()
+            }
+            Message::AutoRefreshToggled(enabled) => {
+                self.auto_refresh = enabled;
+                Command::none()
+            }
+            Message::RefreshIntervalChanged(seconds) => {
+                self.refresh_interval = seconds;
+                Command::none()
+            }
+            Message::Export(format) => match &self.state {
+                AppState::Loaded(_, chart, _) => {
+                    let selected =
+                        chart.data[chart.bottom as usize..=chart.top as usize].to_vec();
+                    let contents = serialize(&selected, format);
+                    Command::perform(export(contents, format), |_| Message::None)
+                }
+                _ => Command::none(),
+            },
             Message::None => Command::none(),
         }
     }
 
     fn view(&self) -> Element<'_, Self::Message, iced::Renderer<Self::Theme>> {
-        let choose_theme = [ThemeType::Light, ThemeType::Dark].iter().fold(
+        let choose_theme = self.themes.iter().enumerate().fold(
             column![text("Choose a theme:")].spacing(8),
-            |column, theme| {
+            |column, (index, palette)| {
                 column.push(radio(
-                    format!("{theme:?}"),
-                    *theme,
-                    Some(self.theme),
+                    palette.name.clone(),
+                    index,
+                    Some(self.selected_theme),
                     Message::ThemeChanged,
                 ))
             },
@@ -387,13 +997,13 @@ impl Application for App {
         let content = match &self.state {
             AppState::Loading => column![text("Loading...")],
             AppState::Loaded(state, chart, data_len) => {
+                let bold_font = Font {
+                    family: iced::font::Family::Name("Montserrat"),
+                    weight: iced::font::Weight::Bold,
+                    stretch: iced::font::Stretch::Normal,
+                    monospaced: false,
+                };
                 let text_column = {
-                    let bold_font = Font {
-                        family: iced::font::Family::Name("Montserrat"),
-                        weight: iced::font::Weight::Bold,
-                        stretch: iced::font::Stretch::Normal,
-                        monospaced: false,
-                    };
                     column![
                         row![text("Co2:").font(bold_font), text(state.co2)].spacing(8),
                         row![text("TVOC:").font(bold_font), text(state.tvoc)].spacing(8),
@@ -434,10 +1044,145 @@ impl Application for App {
                         text(chart.data[chart.top as usize].time.format("%H:%M"))
                     ]
                 ];
+                let series_toggles = column![text("Series:")]
+                    .spacing(8)
+                    .push(toggler(
+                        "CO2".to_string(),
+                        chart.show_co2,
+                        Message::Co2Toggled,
+                    ))
+                    .push(toggler(
+                        "TVOC".to_string(),
+                        chart.show_tvoc,
+                        Message::TvocToggled,
+                    ));
+                let auto_refresh = column![text("Auto-refresh:")]
+                    .spacing(8)
+                    .push(toggler(
+                        format!("Every {}s", self.refresh_interval),
+                        self.auto_refresh,
+                        Message::AutoRefreshToggled,
+                    ))
+                    .push(
+                        container(slider(
+                            5..=300,
+                            self.refresh_interval,
+                            Message::RefreshIntervalChanged,
+                        ))
+                        .width(200),
+                    );
+                let thresholds = column![text("CO2 thresholds:")]
+                    .spacing(8)
+                    .push(
+                        row![
+                            text("Moderate"),
+                            text_input("", &self.thresholds[0].to_string())
+                                .on_input(Message::ModerateThresholdChanged)
+                                .width(80)
+                        ]
+                        .spacing(8),
+                    )
+                    .push(
+                        row![
+                            text("Unhealthy"),
+                            text_input("", &self.thresholds[1].to_string())
+                                .on_input(Message::UnhealthyThresholdChanged)
+                                .width(80)
+                        ]
+                        .spacing(8),
+                    );
+
+                // Match the band normalization so the banner and the shaded
+                // zones agree when the threshold inputs are inverted.
+                let [lo, hi] = {
+                    let mut t = self.thresholds;
+                    t.sort_unstable();
+                    t
+                };
+                let alert_unhealthy =
+                    state.co2 >= hi || matches!(state.qi, QualityIndex::Unhealthy);
+                let alert_poor = state.co2 >= lo || matches!(state.qi, QualityIndex::Poor);
+                let banner: Element<Message> = if alert_unhealthy {
+                    container(
+                        text(format!("Unhealthy air — CO2 at {} ppm", state.co2))
+                            .style(Color::from_rgb(0.86, 0.24, 0.24)),
+                    )
+                    .padding(8)
+                    .into()
+                } else if alert_poor {
+                    container(
+                        text(format!("Poor air — CO2 at {} ppm", state.co2))
+                            .style(Color::from_rgb(0.90, 0.70, 0.0)),
+                    )
+                    .padding(8)
+                    .into()
+                } else {
+                    vertical_space(0).into()
+                };
+
+                // Stats strip over the currently selected slider range.
+                let selected = &chart.data[chart.bottom as usize..=chart.top as usize];
+                let stats_strip = |metric: Metric| {
+                    let s = stats(selected, metric);
+                    let tile = |label: &str, value: f32| {
+                        column![
+                            text(label.to_string()).font(bold_font).size(12),
+                            text(format!("{value:.0}"))
+                        ]
+                        .spacing(2)
+                    };
+                    row![
+                        tile("Min", s.min),
+                        tile("Max", s.max),
+                        tile("Mean", s.mean),
+                        tile("Latest", s.latest),
+                    ]
+                    .spacing(16)
+                };
+
+                let tab_bar = [
+                    ("Current", Tab::Current),
+                    ("CO2", Tab::Co2),
+                    ("TVOC", Tab::Tvoc),
+                    ("Quality", Tab::Quality),
+                ]
+                .iter()
+                .fold(row![].spacing(8), |row, (label, tab)| {
+                    row.push(button(text(*label)).on_press(Message::TabSelected(*tab)))
+                });
+
+                let body: Element<Message> = match self.active_tab {
+                    Tab::Current => text_column.into(),
+                    Tab::Co2 => column![stats_strip(Metric::Co2), chart.view()]
+                        .spacing(8)
+                        .into(),
+                    Tab::Tvoc => column![stats_strip(Metric::Tvoc), chart.view()]
+                        .spacing(8)
+                        .into(),
+                    Tab::Quality => column![stats_strip(Metric::Quality), chart.view()]
+                        .spacing(8)
+                        .into(),
+                };
+
+                // The series togglers only affect the CO2 tab (`is_co2`), so
+                // keep them out of the control row on the other tabs.
+                let controls = if matches!(self.active_tab, Tab::Co2) {
+                    row![sliders, choose_theme, series_toggles, auto_refresh, thresholds].spacing(8)
+                } else {
+                    row![sliders, choose_theme, auto_refresh, thresholds].spacing(8)
+                };
+
                 column![
-                    row![text_column, sliders, choose_theme].spacing(8),
-                    button("Refresh").on_press(Message::Load),
-                    chart.view()
+                    banner,
+                    tab_bar,
+                    controls,
+                    row![
+                        button("Refresh").on_press(Message::Load),
+                        button("Export CSV").on_press(Message::Export(Format::Csv)),
+                        button("Export JSON").on_press(Message::Export(Format::Json)),
+                    ]
+                    .spacing(8),
+                    body
                 ]
                 .spacing(16)
             }
@@ -452,3 +1197,25 @@ impl Application for App {
             .into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::lttb_indices;
+
+    #[test]
+    fn lttb_returns_input_below_threshold() {
+        let values: Vec<f32> = (0..10).map(|i| i as f32).collect();
+        assert_eq!(lttb_indices(&values, 500), (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn lttb_keeps_endpoints_and_length() {
+        let values: Vec<f32> = (0..2000).map(|i| (i as f32 * 0.1).sin()).collect();
+        let indices = lttb_indices(&values, 100);
+        assert_eq!(indices.len(), 100);
+        assert_eq!(*indices.first().unwrap(), 0);
+        assert_eq!(*indices.last().unwrap(), values.len() - 1);
+        // Indices stay in-bounds and ascending.
+        assert!(indices.windows(2).all(|w| w[0] < w[1]));
+    }
+}